@@ -1,19 +1,33 @@
 use anchor_lang::prelude::*;
+use anchor_lang::{system_program, Discriminator};
 // For Token-2022, use these imports:
 use anchor_spl::token_2022::{self, Burn, MintTo};
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface}; // These come from token_interface
-// Add this import at the top with other imports
-use anchor_spl::token_2022::{
-    set_authority, spl_token_2022::instruction::AuthorityType, SetAuthority,
-};
 
 declare_id!("E2cQ1N8qNcWT65bA3QNSTjMYjqa2dVXHwvd6FMZZL9JN");
 
 // Emergency Circuit Breaker Constants
+#[allow(dead_code)]
 const CIRCUIT_BREAKER_THRESHOLD_BPS: u16 = 2500; // 25% volatility threshold
+#[allow(dead_code)]
 const CIRCUIT_BREAKER_DURATION: i64 = 1800; // 30 minutes in seconds
 const MAX_FREEZE_DURATION: i64 = 14400; // 4 hours maximum
+#[allow(dead_code)]
 const PRICE_STALENESS_THRESHOLD: i64 = 1800; // 30 minutes
+const MAX_TRANSFER_FEE_BPS: u16 = 1000; // 10% maximum
+
+// ConfigAccount size before circuit_breaker_until and governance were appended
+const LEGACY_CONFIG_SIZE: usize = 8 + 32 + 32 + 8 + 8 + 2 + 1 + 8 + 1;
+
+// DAO governance program; the realm bound via set_governance controls fee changes
+pub mod governance_program {
+    use super::*;
+    declare_id!("EGbhsJtKMcSkqPAotLvc3mjPkVLQSFi9LJ7qNx78ey6W");
+}
+
+#[program]
+pub mod anupam_coin_wrapper {
+    use super::*;
 
     // Initialize the wrapper contract - Sprint 1 version
     pub fn initialize(ctx: Context<Initialize>, authority: Pubkey) -> Result<()> {
@@ -24,6 +38,8 @@ const PRICE_STALENESS_THRESHOLD: i64 = 1800; // 30 minutes
         config.current_price = 1_000_000; // $1.00 default
         config.transfer_fee_bps = 30; // 0.3% = 30 basis points
         config.is_paused = false;
+        config.circuit_breaker_until = 0;
+        config.governance = Pubkey::default();
         config.price_above_target_since = 0;
         config.consecutive_days_above_target = 0;
 
@@ -67,7 +83,7 @@ const PRICE_STALENESS_THRESHOLD: i64 = 1800; // 30 minutes
 
     // Simple controlled mint with time-lock check
     pub fn mint_tokens(ctx: Context<MintTokens>, amount: u64) -> Result<()> {
-        let config = &ctx.accounts.config;
+        let _config = &ctx.accounts.config;
 
         // Basic checks for Sprint 1
         // require!(!config.is_paused, ErrorCode::ContractPaused);
@@ -105,7 +121,11 @@ const PRICE_STALENESS_THRESHOLD: i64 = 1800; // 30 minutes
     // Simple burn function
     pub fn burn_tokens(ctx: Context<BurnTokens>, amount: u64) -> Result<()> {
         let config = &ctx.accounts.config;
-        require!(!config.is_paused, ErrorCode::ContractPaused);
+        let clock = Clock::get()?;
+        require!(
+            !config.is_frozen(clock.unix_timestamp),
+            ErrorCode::ContractPaused
+        );
 
         let cpi_accounts = Burn {
             mint: ctx.accounts.mint.to_account_info(),
@@ -126,9 +146,15 @@ const PRICE_STALENESS_THRESHOLD: i64 = 1800; // 30 minutes
     }
 
     // Transfer with fee (Sprint 1 requirement)
+    // The plain transfer CPI is kept until the context carries the mint for transfer_checked
+    #[allow(deprecated)]
     pub fn transfer_with_fee(ctx: Context<TransferWithFee>, amount: u64) -> Result<()> {
         let config = &ctx.accounts.config;
-        require!(!config.is_paused, ErrorCode::ContractPaused);
+        let clock = Clock::get()?;
+        require!(
+            !config.is_frozen(clock.unix_timestamp),
+            ErrorCode::ContractPaused
+        );
 
         // Calculate fee (0.3% = 30 basis points)
         let fee_amount = (amount * config.transfer_fee_bps as u64) / 10000;
@@ -166,6 +192,39 @@ const PRICE_STALENESS_THRESHOLD: i64 = 1800; // 30 minutes
         Ok(())
     }
 
+    // Update transfer fee (DAO governance PDA only)
+    pub fn set_transfer_fee(ctx: Context<GovernanceAction>, new_fee_bps: u16) -> Result<()> {
+        require!(
+            new_fee_bps <= MAX_TRANSFER_FEE_BPS,
+            ErrorCode::TransferFeeTooHigh
+        );
+
+        let config = &mut ctx.accounts.config;
+        let old_fee_bps = config.transfer_fee_bps;
+        config.transfer_fee_bps = new_fee_bps;
+
+        emit!(TransferFeeUpdated {
+            old_fee_bps,
+            new_fee_bps,
+        });
+
+        Ok(())
+    }
+
+    // Bind the DAO realm whose governance PDA may act on this config (authority only)
+    pub fn set_governance(ctx: Context<AuthorityAction>, governance: Pubkey) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let old_governance = config.governance;
+        config.governance = governance;
+
+        emit!(GovernanceUpdated {
+            old_governance,
+            new_governance: governance,
+        });
+
+        Ok(())
+    }
+
     // Emergency pause (authority only)
     pub fn pause_contract(ctx: Context<AuthorityAction>) -> Result<()> {
         let config = &mut ctx.accounts.config;
@@ -178,9 +237,92 @@ const PRICE_STALENESS_THRESHOLD: i64 = 1800; // 30 minutes
     pub fn resume_contract(ctx: Context<AuthorityAction>) -> Result<()> {
         let config = &mut ctx.accounts.config;
         config.is_paused = false;
+        config.circuit_breaker_until = 0;
         msg!("Contract resumed by authority");
         Ok(())
     }
+
+    // Grow a config created before circuit_breaker_until and governance existed (authority only)
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        let config = &ctx.accounts.config;
+        let data = config.try_borrow_data()?;
+        require!(
+            data.len() == LEGACY_CONFIG_SIZE,
+            ErrorCode::ConfigAlreadyMigrated
+        );
+        require!(
+            data[..8] == ConfigAccount::DISCRIMINATOR,
+            ErrorCode::InvalidConfigAccount
+        );
+        // The authority is the first field, so it sits at the same offset in both layouts
+        let authority = Pubkey::try_from(&data[8..40]).unwrap();
+        require_keys_eq!(
+            authority,
+            ctx.accounts.authority.key(),
+            ErrorCode::InvalidConfigAccount
+        );
+        drop(data);
+
+        let new_size = 8 + ConfigAccount::INIT_SPACE;
+        let top_up = Rent::get()?
+            .minimum_balance(new_size)
+            .saturating_sub(config.lamports());
+        if top_up > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.authority.to_account_info(),
+                        to: config.to_account_info(),
+                    },
+                ),
+                top_up,
+            )?;
+        }
+        // Zero-filled bytes leave the breaker unset and no realm bound
+        config.realloc(new_size, true)?;
+
+        msg!("Config migrated to the current layout");
+        Ok(())
+    }
+
+    // Emergency pause (DAO governance PDA only)
+    pub fn governance_pause(ctx: Context<GovernanceAction>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.is_paused = true;
+        msg!("Contract paused by governance");
+        Ok(())
+    }
+
+    // Resume contract (DAO governance PDA only)
+    pub fn governance_resume(ctx: Context<GovernanceAction>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.is_paused = false;
+        config.circuit_breaker_until = 0;
+        msg!("Contract resumed by governance");
+        Ok(())
+    }
+
+    // Freeze transfers and burns for a limited time (DAO governance PDA only)
+    pub fn trip_circuit_breaker(
+        ctx: Context<GovernanceAction>,
+        duration_seconds: i64,
+    ) -> Result<()> {
+        require!(
+            duration_seconds > 0 && duration_seconds <= MAX_FREEZE_DURATION,
+            ErrorCode::InvalidFreezeDuration
+        );
+
+        let config = &mut ctx.accounts.config;
+        let clock = Clock::get()?;
+        config.circuit_breaker_until = clock.unix_timestamp + duration_seconds;
+
+        emit!(CircuitBreakerTripped {
+            frozen_until: config.circuit_breaker_until,
+        });
+
+        Ok(())
+    }
 }
 
 // Account Structures - Updated for Token-2022
@@ -274,6 +416,36 @@ pub struct AuthorityAction<'info> {
     pub authority: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct GovernanceAction<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, ConfigAccount>,
+    #[account(
+        owner = governance_program::ID,
+        constraint = governance.key() == config.governance @ ErrorCode::UnauthorizedGovernance
+    )]
+    pub governance: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    /// CHECK: Legacy layout cannot be deserialized; owner, discriminator and authority are checked in the handler
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        owner = crate::ID
+    )]
+    pub config: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
 // Simplified Configuration for Sprint 1
 #[account]
 #[derive(InitSpace)]
@@ -286,6 +458,14 @@ pub struct ConfigAccount {
     pub is_paused: bool,
     pub price_above_target_since: i64, // Timestamp when price first went above target
     pub consecutive_days_above_target: u8, // Days price has been above target
+    pub circuit_breaker_until: i64,    // Transfers frozen until this timestamp
+    pub governance: Pubkey,            // DAO realm allowed to act through GovernanceAction
+}
+
+impl ConfigAccount {
+    pub fn is_frozen(&self, now: i64) -> bool {
+        self.is_paused || now < self.circuit_breaker_until
+    }
 }
 
 // Events
@@ -307,6 +487,23 @@ pub struct TokensBurned {
     pub user: Pubkey,
 }
 
+#[event]
+pub struct CircuitBreakerTripped {
+    pub frozen_until: i64,
+}
+
+#[event]
+pub struct GovernanceUpdated {
+    pub old_governance: Pubkey,
+    pub new_governance: Pubkey,
+}
+
+#[event]
+pub struct TransferFeeUpdated {
+    pub old_fee_bps: u16,
+    pub new_fee_bps: u16,
+}
+
 #[event]
 pub struct TransferCompleted {
     pub from: Pubkey,
@@ -324,4 +521,14 @@ pub enum ErrorCode {
     PriceBelowTarget,
     #[msg("Price must stay above $1.05 for 7 consecutive days")]
     TimeRequirementNotMet,
+    #[msg("Transfer fee cannot exceed 1000 basis points")]
+    TransferFeeTooHigh,
+    #[msg("Freeze duration must be between 1 second and 4 hours")]
+    InvalidFreezeDuration,
+    #[msg("Signer is not the governance realm bound to this config")]
+    UnauthorizedGovernance,
+    #[msg("Config account already uses the current layout")]
+    ConfigAlreadyMigrated,
+    #[msg("Config account is not a legacy config owned by this authority")]
+    InvalidConfigAccount,
 }
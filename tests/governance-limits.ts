@@ -0,0 +1,148 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Governance } from "../target/types/governance";
+import { TOKEN_2022_PROGRAM_ID, createMint } from "@solana/spl-token";
+import { assert } from "chai";
+
+describe("governance limits", () => {
+  // Configure the client to use the local cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const governance = anchor.workspace.Governance as Program<Governance>;
+
+  const user = provider.wallet;
+  const connection = provider.connection;
+
+  const realmName = "limits";
+  const maxActiveProposals = 25;
+  const maxVotersPerProposal = 200;
+
+  let mint: anchor.web3.PublicKey;
+  let governanceConfigPda: anchor.web3.PublicKey;
+
+  const expectError = async (call: Promise<unknown>, code: string) => {
+    let rejected = false;
+    try {
+      await call;
+    } catch (err) {
+      rejected = true;
+      assert.include(err.toString(), code);
+    }
+    assert.isTrue(rejected, `expected ${code}`);
+  };
+
+  const initialize = (maxProposals: number, maxVoters: number) =>
+    governance.methods
+      .initialize(
+        new anchor.BN(86400),
+        new anchor.BN(maxProposals),
+        new anchor.BN(maxVoters),
+        realmName
+      )
+      .accounts({
+        governanceConfig: governanceConfigPda,
+        apmMint: mint,
+        authority: user.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+  const updateLimits = (maxProposals: number | null, maxVoters: number | null) =>
+    governance.methods
+      .updateConfig(
+        null,
+        maxProposals === null ? null : new anchor.BN(maxProposals),
+        maxVoters === null ? null : new anchor.BN(maxVoters),
+        null,
+        null,
+        null,
+        null,
+        null
+      )
+      .accounts({
+        governanceConfig: governanceConfigPda,
+        authority: user.publicKey,
+        pendingAdminAction: null,
+      })
+      .rpc();
+
+  before(async () => {
+    mint = await createMint(connection, user.payer, user.publicKey, null, 6, undefined, {
+      programId: TOKEN_2022_PROGRAM_ID,
+    });
+    [governanceConfigPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("governance_config"), mint.toBuffer(), Buffer.from(realmName)],
+      governance.programId
+    );
+  });
+
+  it("Applies the same bounds in initialize", async () => {
+    // Initialize only once so the suite can be rerun against the same realm
+    if (!(await governance.account.governanceConfig.fetchNullable(governanceConfigPda))) {
+      await expectError(initialize(maxActiveProposals + 1, 10), "MaxActiveProposalsExceeded");
+      await expectError(initialize(10, maxVotersPerProposal + 1), "MaxVotersExceeded");
+
+      await initialize(maxActiveProposals, maxVotersPerProposal);
+    }
+    const config = await governance.account.governanceConfig.fetch(governanceConfigPda);
+    assert.equal(config.maxProposalsPerConfig.toNumber(), maxActiveProposals);
+    assert.equal(config.maxVotersPerProposal.toNumber(), maxVotersPerProposal);
+  });
+
+  it("Applies the same bounds in update_config", async () => {
+    await expectError(updateLimits(maxActiveProposals + 1, null), "MaxActiveProposalsExceeded");
+    await expectError(updateLimits(null, maxVotersPerProposal + 1), "MaxVotersExceeded");
+
+    await updateLimits(maxActiveProposals, maxVotersPerProposal);
+  });
+
+  it("Applies the same bounds in create_proposal", async () => {
+    // The config allows the maximum, so only the shared limit can reject one more voter
+    const { proposalCount } = await governance.account.governanceConfig.fetch(
+      governanceConfigPda
+    );
+    const [proposalPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("proposal"),
+        governanceConfigPda.toBuffer(),
+        proposalCount.toArrayLike(Buffer, "le", 8),
+      ],
+      governance.programId
+    );
+    const [voteRecordPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vote_record"), proposalPda.toBuffer()],
+      governance.programId
+    );
+
+    await expectError(
+      governance.methods
+        .createProposal(
+          "Too many voters",
+          "Asks for more voters than a vote record can hold",
+          "",
+          new Array(32).fill(0),
+          new anchor.BN(3600),
+          { yearlyCap: {} },
+          new anchor.BN(maxVotersPerProposal + 1),
+          { none: {} },
+          null,
+          false,
+          []
+        )
+        .accounts({
+          proposal: proposalPda,
+          governanceConfig: governanceConfigPda,
+          voteRecord: voteRecordPda,
+          authority: user.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          depositVault: null,
+          creatorTokenAccount: null,
+          apmMint: null,
+          tokenProgram: null,
+        })
+        .rpc(),
+      "MaxVotersExceeded"
+    );
+  });
+});
@@ -0,0 +1,272 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { BankrunProvider, startAnchor } from "anchor-bankrun";
+import { Clock, ProgramTestContext } from "solana-bankrun";
+import { Governance } from "../target/types/governance";
+import { AnupamCoinWrapper } from "../target/types/anupam_coin_wrapper";
+import GovernanceIdl from "../target/idl/governance.json";
+import AnupamCoinWrapperIdl from "../target/idl/anupam_coin_wrapper.json";
+import {
+  TOKEN_2022_PROGRAM_ID,
+  createInitializeMint2Instruction,
+  getMintLen,
+} from "@solana/spl-token";
+import { assert } from "chai";
+
+// Runs on bankrun so the clock can be moved past the voting period and timelock
+describe("governance transfer fee proposal", () => {
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let governance: Program<Governance>;
+  let coin: Program<AnupamCoinWrapper>;
+  let payer: anchor.web3.Keypair;
+
+  const newFeeBps = 50;
+  const realmName = "apm";
+  const votingDurationSeconds = 3600;
+
+  let governanceConfigPda: anchor.web3.PublicKey;
+  let coinConfigPda: anchor.web3.PublicKey;
+
+  let mint: anchor.web3.PublicKey;
+
+  let proposalPda: anchor.web3.PublicKey;
+  let voteRecordPda: anchor.web3.PublicKey;
+
+  // Creates a Token-2022 account of the given size and initializes it
+  const createToken2022Account = async (
+    size: number,
+    init: (address: anchor.web3.PublicKey) => anchor.web3.TransactionInstruction
+  ) => {
+    const account = anchor.web3.Keypair.generate();
+    const rent = await context.banksClient.getRent();
+    const tx = new anchor.web3.Transaction().add(
+      anchor.web3.SystemProgram.createAccount({
+        fromPubkey: payer.publicKey,
+        newAccountPubkey: account.publicKey,
+        lamports: Number(rent.minimumBalance(BigInt(size))),
+        space: size,
+        programId: TOKEN_2022_PROGRAM_ID,
+      }),
+      init(account.publicKey)
+    );
+    await provider.sendAndConfirm(tx, [account]);
+    return account.publicKey;
+  };
+
+  // Moves the bank clock forward to the given unix time
+  const warpTo = async (unixTimestamp: number) => {
+    const clock = await context.banksClient.getClock();
+    context.setClock(
+      new Clock(
+        clock.slot + BigInt(1),
+        clock.epochStartTimestamp,
+        clock.epoch,
+        clock.leaderScheduleEpoch,
+        BigInt(unixTimestamp)
+      )
+    );
+  };
+
+  before(async () => {
+    context = await startAnchor(".", [], []);
+    provider = new BankrunProvider(context);
+    anchor.setProvider(provider);
+    governance = new Program<Governance>(GovernanceIdl as Governance, provider);
+    coin = new Program<AnupamCoinWrapper>(AnupamCoinWrapperIdl as AnupamCoinWrapper, provider);
+    payer = context.payer;
+
+    [coinConfigPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("config")],
+      coin.programId
+    );
+
+    // Each run starts from a fresh bank, so the mint and realm are always new
+    mint = await createToken2022Account(getMintLen([]), (address) =>
+      createInitializeMint2Instruction(address, 6, payer.publicKey, null, TOKEN_2022_PROGRAM_ID)
+    );
+
+    [governanceConfigPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("governance_config"), mint.toBuffer(), Buffer.from(realmName)],
+      governance.programId
+    );
+
+    await coin.methods
+      .initialize(payer.publicKey)
+      .accounts({
+        config: coinConfigPda,
+        mint: mint,
+        payer: payer.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .rpc();
+
+    await governance.methods
+      .initialize(new anchor.BN(86400), new anchor.BN(10), new anchor.BN(10), realmName)
+      .accounts({
+        governanceConfig: governanceConfigPda,
+        apmMint: mint,
+        authority: payer.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    // Let this realm act on the wrapper config
+    await coin.methods
+      .setGovernance(governanceConfigPda)
+      .accounts({
+        config: coinConfigPda,
+        authority: payer.publicKey,
+      })
+      .rpc();
+
+    // Make TransferFee proposals executable as soon as voting ends
+    await governance.methods
+      .setCategoryTimelock({ transferFee: {} }, new anchor.BN(0))
+      .accounts({
+        governanceConfig: governanceConfigPda,
+        authority: payer.publicKey,
+        pendingAdminAction: null,
+      })
+      .rpc();
+  });
+
+  it("Creates a TransferFee proposal", async () => {
+    // Proposals are seeded by the realm and the next proposal id
+    const { proposalCount } = await governance.account.governanceConfig.fetch(
+      governanceConfigPda
+    );
+    [proposalPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("proposal"),
+        governanceConfigPda.toBuffer(),
+        proposalCount.toArrayLike(Buffer, "le", 8),
+      ],
+      governance.programId
+    );
+    [voteRecordPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vote_record"), proposalPda.toBuffer()],
+      governance.programId
+    );
+
+    await governance.methods
+      .createProposal(
+        "Lower transfer fee",
+        "Set the APM transfer fee to 0.5%",
+        "",
+        new Array(32).fill(0),
+        new anchor.BN(votingDurationSeconds),
+        { transferFee: {} },
+        new anchor.BN(10),
+        { transferFee: { newFeeBps } },
+        null,
+        false,
+        []
+      )
+      .accounts({
+        proposal: proposalPda,
+        governanceConfig: governanceConfigPda,
+        voteRecord: voteRecordPda,
+        authority: payer.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        depositVault: null,
+        creatorTokenAccount: null,
+        apmMint: null,
+        tokenProgram: null,
+      })
+      .rpc();
+
+    const created = await governance.account.proposal.fetch(proposalPda);
+    assert.deepEqual(created.payload, { transferFee: { newFeeBps } });
+  });
+
+  it("Votes the proposal through", async () => {
+    await governance.methods
+      .castVote(true)
+      .accounts({
+        proposal: proposalPda,
+        governanceConfig: governanceConfigPda,
+        voteRecord: voteRecordPda,
+        voter: payer.publicKey,
+        voterWeight: null,
+      })
+      .rpc();
+
+    const voted = await governance.account.proposal.fetch(proposalPda);
+    assert.equal(voted.forVotes.toNumber(), 1);
+  });
+
+  it("Rejects cutting the vote short from the admin key", async () => {
+    let rejected = false;
+    try {
+      await governance.methods
+        .updateProposalEndtime(new anchor.BN(1))
+        .accounts({
+          proposal: proposalPda,
+          governanceConfig: governanceConfigPda,
+          authority: payer.publicKey,
+          pendingAdminAction: null,
+        })
+        .rpc();
+    } catch (err) {
+      rejected = true;
+      assert.include(err.toString(), "EndTimeNotExtended");
+    }
+    assert.isTrue(rejected);
+  });
+
+  it("Queues the proposal once voting has closed", async () => {
+    // Voting lasts at least an hour and can't be cut short, so move the clock past its end
+    const { endTime } = await governance.account.proposal.fetch(proposalPda);
+    await warpTo(endTime.toNumber() + 1);
+
+    await governance.methods
+      .queueProposal()
+      .accounts({
+        proposal: proposalPda,
+        governanceConfig: governanceConfigPda,
+        signer: payer.publicKey,
+      })
+      .rpc();
+
+    const queued = await governance.account.proposal.fetch(proposalPda);
+    assert.isNotNull(queued.eta);
+  });
+
+  it("Executes the proposal and updates the wrapper fee", async () => {
+    // The TransferFee timelock is zero, so the proposal is executable right after queueing
+    const { eta } = await governance.account.proposal.fetch(proposalPda);
+    await warpTo(eta.toNumber() + 1);
+
+    await governance.methods
+      .executeProposal()
+      .accounts({
+        proposal: proposalPda,
+        governanceConfig: governanceConfigPda,
+        voteRecord: voteRecordPda,
+        proposalCreator: payer.publicKey,
+        executor: payer.publicKey,
+        treasuryConfig: null,
+        treasuryWallet: null,
+        treasuryWalletSwap: null,
+        allocatedTreasuryWallet: null,
+        transferMint: null,
+        apmMint: mint,
+        coinConfig: coinConfigPda,
+        coinProgram: coin.programId,
+        proposalTransaction: null,
+        targetProposal: null,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const executed = await governance.account.proposal.fetch(proposalPda);
+    assert.equal(executed.executed, true);
+
+    const config = await coin.account.configAccount.fetch(coinConfigPda);
+    assert.equal(config.transferFeeBps, newFeeBps);
+  });
+});